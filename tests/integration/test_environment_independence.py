@@ -0,0 +1,79 @@
+"""
+Integration tests for environment independence of directory SWHIDs.
+
+Directory payloads are re-run under the C locale and a restrictive umask;
+the resulting SWHIDs must still match the expectations in config.yaml.
+"""
+
+import os
+
+import pytest
+
+from harness.config import HarnessConfig
+from harness.plugins.discovery import ImplementationDiscovery
+from harness.resource_manager import ResourceManager
+
+
+def _directory_payloads():
+    """Return directory payloads that have a v1 expectation."""
+    config = HarnessConfig.load_from_file("config.yaml")
+    return [
+        payload
+        for category, payload in config.get_all_payloads()
+        if (category == "directory" or category.startswith("directory/"))
+        and payload.expected_swhid
+    ]
+
+
+def _compute(impl, payload, resource_manager):
+    """Compute the directory SWHID of a payload, or None on failure."""
+    payload_path = resource_manager.extract_tarball_if_needed(payload.path, os.getcwd())
+    try:
+        return impl.compute_swhid(payload_path, "directory")
+    except Exception:
+        return None
+
+
+class TestEnvironmentIndependence:
+    """Directory SWHIDs must not depend on locale or umask."""
+
+    def test_directory_swhids_independent_of_locale_and_umask(self, monkeypatch):
+        """Re-run directory payloads with LC_ALL=C and umask 077."""
+        discovery = ImplementationDiscovery("implementations")
+        implementations = {
+            name: impl
+            for name, impl in discovery.discover_implementations().items()
+            if impl.is_available() and "dir" in impl.get_capabilities().supported_types
+        }
+        if not implementations:
+            pytest.skip("No implementation supporting directories is available")
+
+        payloads = _directory_payloads()
+        resource_manager = ResourceManager()
+        failures = []
+        try:
+            for name, impl in implementations.items():
+                # Only payloads the implementation gets right in the default
+                # environment can reveal an environment dependency
+                baseline = [
+                    payload for payload in payloads
+                    if _compute(impl, payload, resource_manager) == payload.expected_swhid
+                ]
+
+                with monkeypatch.context() as m:
+                    m.setenv("LC_ALL", "C")
+                    m.setenv("LANG", "C")
+                    old_umask = os.umask(0o077)
+                    try:
+                        for payload in baseline:
+                            swhid = _compute(impl, payload, resource_manager)
+                            if swhid != payload.expected_swhid:
+                                failures.append(
+                                    f"{name}/{payload.name}: expected {payload.expected_swhid}, got {swhid}"
+                                )
+                    finally:
+                        os.umask(old_umask)
+        finally:
+            resource_manager.cleanup_temp_dirs()
+
+        assert not failures, "SWHIDs changed under LC_ALL=C / umask 077:\n" + "\n".join(failures)